@@ -1,18 +1,93 @@
 use dialoguer::MultiSelect;
-use git2::{Repository, Status};
-use std::{path::Path, process};
+use git2::{IndexAddOption, Repository, Status, StatusOptions};
+use notify::{RecursiveMode, Watcher};
+use std::{
+    collections::HashSet,
+    path::Path,
+    process,
+    sync::mpsc::channel,
+};
 
 #[derive(Clone, Debug)]
 pub struct PathItems {
     path: String,
     is_staged: bool,
     is_selected: bool,
+    status_code: String,
+}
+
+/// User-controllable knobs for the `git2::StatusOptions` that back
+/// `get_paths`, so callers (CLI flags) can opt into showing ignored files,
+/// skip untracked files, or treat submodules as unmodified.
+#[derive(Clone, Copy, Debug)]
+pub struct StatusQueryOptions {
+    pub include_untracked: bool,
+    pub include_ignored: bool,
+    pub recurse_untracked_dirs: bool,
+    pub ignore_submodules: bool,
+}
+
+impl Default for StatusQueryOptions {
+    fn default() -> Self {
+        StatusQueryOptions {
+            include_untracked: true,
+            include_ignored: false,
+            recurse_untracked_dirs: true,
+            ignore_submodules: true,
+        }
+    }
+}
+
+/// Derives a short porcelain-style status code (index side + worktree side)
+/// from a file's raw `git2::Status` flags, e.g. `"M "`, `"A "`, `" M"`, `"??"`.
+fn status_code(status: Status) -> String {
+    if status.contains(Status::WT_NEW) {
+        return String::from("??");
+    }
+
+    let index_side = if status.contains(Status::INDEX_NEW) {
+        "A"
+    } else if status.contains(Status::INDEX_MODIFIED) {
+        "M"
+    } else if status.contains(Status::INDEX_DELETED) {
+        "D"
+    } else if status.contains(Status::INDEX_RENAMED) {
+        "R"
+    } else if status.contains(Status::INDEX_TYPECHANGE) {
+        "T"
+    } else {
+        " "
+    };
+
+    let worktree_side = if status.contains(Status::WT_MODIFIED) {
+        "M"
+    } else if status.contains(Status::WT_DELETED) {
+        "D"
+    } else if status.contains(Status::WT_RENAMED) {
+        "R"
+    } else if status.contains(Status::WT_TYPECHANGE) {
+        "T"
+    } else {
+        " "
+    };
+
+    format!("{}{}", index_side, worktree_side)
 }
 
 // Step 1
 /// Gets the file paths of the changes in your repo.
-pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
-    let statuses = repo.statuses(None)?;
+pub fn get_paths(
+    repo: &Repository,
+    options: &StatusQueryOptions,
+) -> Result<Vec<PathItems>, git2::Error> {
+    let mut status_options = StatusOptions::new();
+    status_options
+        .include_untracked(options.include_untracked)
+        .include_ignored(options.include_ignored)
+        .recurse_untracked_dirs(options.recurse_untracked_dirs)
+        .exclude_submodules(options.ignore_submodules);
+
+    let statuses = repo.statuses(Some(&mut status_options))?;
 
     if statuses.is_empty() {
         println!("{}", console::style("✔ working tree clean ✔").green());
@@ -22,10 +97,12 @@ pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
     let mut items: Vec<PathItems> = vec![];
 
     for diff_entry in statuses.iter() {
-        if diff_entry.status() == Status::IGNORED {
+        if diff_entry.status().contains(Status::IGNORED) && !options.include_ignored {
             continue;
         }
 
+        let code = status_code(diff_entry.status());
+
         let path_items = diff_entry
             // 1. Try to get the HEAD → index diff
             .head_to_index()
@@ -35,6 +112,7 @@ pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
                     path: String::from(d.new_file().path()?.display().to_string()),
                     is_staged: true,
                     is_selected: false,
+                    status_code: code.clone(),
                 })
             })
             // 2. Otherwise, try index → workdir diff (This means the file has unstaged changes.)
@@ -47,6 +125,7 @@ pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
                                 path: String::from(d.new_file().path()?.display().to_string()),
                                 is_staged: false,
                                 is_selected: false,
+                                status_code: code.clone(),
                             })
                         })
                         // 3. If still nothing, try the "old" file's path (maybe a deletion/rename)
@@ -57,6 +136,7 @@ pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
                                     path: String::from(d.old_file().path()?.display().to_string()),
                                     is_staged: false,
                                     is_selected: false,
+                                    status_code: code.clone(),
                                 })
                             })
                         })
@@ -65,6 +145,7 @@ pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
                             path: String::from("<unknown>"),
                             is_staged: false,
                             is_selected: false,
+                            status_code: code.clone(),
                         }),
                 )
             })
@@ -82,6 +163,109 @@ pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
     Ok(items)
 }
 
+/// Output formats for emitting detected changes without the interactive
+/// prompt, so the results can be piped into editors, fzf, or other scripts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Short `XY path` lines, one per entry, matching `git status --short`.
+    Porcelain { nul_separated: bool },
+    /// A JSON array of `{path, status, staged}` objects.
+    Json,
+}
+
+/// Renders the detected changes in the given `OutputFormat`, sharing the
+/// same `status_code` derivation used by the interactive prompt.
+/// # Arguments
+/// * `path_items` - The detected changes, as returned by `get_paths`.
+/// * `format` - The output format to render.
+pub fn format_paths(path_items: &[PathItems], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Porcelain { nul_separated } => {
+            let separator = if nul_separated { "\0" } else { "\n" };
+
+            path_items
+                .iter()
+                .map(|item| format!("{} {}", item.status_code, item.path))
+                .collect::<Vec<String>>()
+                .join(separator)
+        }
+        OutputFormat::Json => {
+            let entries: Vec<String> = path_items
+                .iter()
+                .map(|item| {
+                    format!(
+                        "{{\"path\":\"{}\",\"status\":\"{}\",\"staged\":{}}}",
+                        escape_json(&item.path),
+                        escape_json(&item.status_code),
+                        item.is_staged
+                    )
+                })
+                .collect();
+
+            format!("[{}]", entries.join(","))
+        }
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Prints the current branch, its ahead/behind divergence from its upstream,
+/// and any in-progress operation (merge/rebase/cherry-pick/etc.), so the user
+/// has context about where their staged changes are headed before picking
+/// files.
+/// # Arguments
+/// * `repo` - A reference to the git repository.
+pub fn print_branch_summary(repo: &Repository) -> Result<(), git2::Error> {
+    let head = repo.head()?;
+    let branch_name = if repo.head_detached()? {
+        "HEAD (detached)"
+    } else {
+        head.shorthand().unwrap_or("HEAD (detached)")
+    };
+
+    print!("{} {}", console::style("On branch").bold(), branch_name);
+
+    if let Some(local_oid) = head.target() {
+        if let Ok(branch) = repo.find_branch(branch_name, git2::BranchType::Local) {
+            if let Ok(upstream) = branch.upstream() {
+                if let Some(upstream_oid) = upstream.get().target() {
+                    if let Ok((ahead, behind)) = repo.graph_ahead_behind(local_oid, upstream_oid) {
+                        print!(" ({} ahead, {} behind)", ahead, behind);
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+
+    let in_progress = match repo.state() {
+        git2::RepositoryState::Clean => None,
+        git2::RepositoryState::Merge => Some("merge"),
+        git2::RepositoryState::Rebase
+        | git2::RepositoryState::RebaseInteractive
+        | git2::RepositoryState::RebaseMerge => Some("rebase"),
+        git2::RepositoryState::CherryPick | git2::RepositoryState::CherryPickSequence => {
+            Some("cherry-pick")
+        }
+        git2::RepositoryState::Revert | git2::RepositoryState::RevertSequence => Some("revert"),
+        git2::RepositoryState::Bisect => Some("bisect"),
+        _ => Some("operation"),
+    };
+
+    if let Some(label) = in_progress {
+        println!(
+            "{} {} in progress",
+            console::style("⚠").yellow(),
+            label
+        );
+    }
+
+    Ok(())
+}
+
 // Step 2
 /// Prompts the user to select files to stage and returns the selected file paths.
 /// If no files are selected, the program exits.
@@ -90,8 +274,10 @@ pub fn get_paths(repo: &Repository) -> Result<Vec<PathItems>, git2::Error> {
 /// # Returns
 /// A vector of selected file paths as strings.
 pub fn choose_files(path_items: Vec<PathItems>) -> Vec<PathItems> {
-    // TODO: Include the status of each file in the prompt (e.g., "M", "A", "D", "??")
-    let list_of_paths: Vec<String> = path_items.iter().map(|p| p.path.clone()).collect();
+    let list_of_paths: Vec<String> = path_items
+        .iter()
+        .map(|p| format!("{}  {}", p.status_code, p.path))
+        .collect();
     let list_of_preselected: Vec<bool> = path_items.iter().map(|p| p.is_staged).collect();
 
     let selections = MultiSelect::new()
@@ -113,6 +299,122 @@ pub fn choose_files(path_items: Vec<PathItems>) -> Vec<PathItems> {
     paths
 }
 
+/// Prompts the user to select files to discard, mirroring `choose_files` but
+/// with nothing preselected, since discarding is opt-in rather than mirroring
+/// the current index state.
+/// # Arguments
+/// * `path_items` - The candidate files and their current status.
+/// # Returns
+/// Every candidate file, with the chosen ones marked as selected.
+pub fn choose_files_to_discard(path_items: Vec<PathItems>) -> Vec<PathItems> {
+    let list_of_paths: Vec<String> = path_items
+        .iter()
+        .map(|p| format!("{}  {}", p.status_code, p.path))
+        .collect();
+
+    let selections = MultiSelect::new()
+        .with_prompt("Choose files to discard")
+        .items(list_of_paths)
+        .interact()
+        .unwrap_or_else(|_| {
+            eprintln!("{}", console::style("Error selecting files").red());
+            process::exit(1)
+        });
+
+    let mut paths: Vec<PathItems> = path_items.clone();
+
+    for index in selections {
+        paths[index].is_selected = true;
+    }
+
+    paths
+}
+
+/// Runs the interactive `choose_files` prompt, watching the working tree for
+/// changes made from another terminal and re-rendering the prompt with fresh
+/// statuses whenever they occur.
+///
+/// `dialoguer`'s `MultiSelect::interact` blocks the terminal for its entire
+/// duration, so we can't redraw mid-keystroke. Instead, any filesystem events
+/// that land while the user is deciding are drained (collapsing a burst of
+/// rapid changes into a single refresh) and, if the tree did change, the
+/// prompt is immediately replayed with up-to-date statuses. Selections are
+/// preserved across replays by path.
+/// # Arguments
+/// * `repo` - A reference to the git repository.
+/// # Returns
+/// Every file from the last prompt, with the chosen ones marked as selected.
+pub fn choose_files_live(
+    repo: &Repository,
+    options: &StatusQueryOptions,
+) -> Result<Vec<PathItems>, git2::Error> {
+    let workdir = repo.workdir().unwrap_or_else(|| {
+        eprintln!(
+            "{}",
+            console::style("Cannot watch a bare repository").red()
+        );
+        process::exit(1)
+    });
+
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            let _ = tx.send(());
+        }
+    })
+    .unwrap_or_else(|_| {
+        eprintln!("{}", console::style("Failed to watch working tree").red());
+        process::exit(1)
+    });
+
+    watcher
+        .watch(workdir, RecursiveMode::Recursive)
+        .unwrap_or_else(|_| {
+            eprintln!("{}", console::style("Failed to watch working tree").red());
+            process::exit(1)
+        });
+
+    let mut selected_paths: HashSet<String> = HashSet::new();
+
+    loop {
+        let mut path_items = get_paths(repo, options)?;
+
+        for item in path_items.iter_mut() {
+            if selected_paths.contains(&item.path) {
+                item.is_selected = true;
+            }
+        }
+
+        if path_items.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let chosen = choose_files(path_items);
+
+        selected_paths = chosen
+            .iter()
+            .filter(|item| item.is_selected)
+            .map(|item| item.path.clone())
+            .collect();
+
+        // Drain every queued event so a burst of rapid saves only triggers
+        // one refresh, not one per event.
+        let mut tree_changed = false;
+        while rx.try_recv().is_ok() {
+            tree_changed = true;
+        }
+
+        if !tree_changed {
+            return Ok(chosen);
+        }
+
+        println!(
+            "{}",
+            console::style("Working tree changed, refreshing file list...").cyan()
+        );
+    }
+}
+
 // Step 3
 /// Stages the selected files in the git repository.
 /// If staging fails, the program exits.
@@ -177,6 +479,95 @@ pub fn git_add_selected(repo: &Repository, paths: &Vec<PathItems>) -> Result<(),
     Ok(())
 }
 
+/// Discards working-tree (and, if staged, index) changes for the selected
+/// files, equivalent to `git restore --staged --worktree` / `git checkout --`.
+/// Unselected files are left untouched.
+/// # Arguments
+/// * `repo` - A reference to the git repository.
+/// * `paths` - A vector of file paths to consider for discarding.
+pub fn git_discard_selected(repo: &Repository, paths: &Vec<PathItems>) -> Result<(), git2::Error> {
+    println!("{}", console::style("Changes Made:").bold());
+
+    let mut logs = vec![];
+
+    for item in paths {
+        if !item.is_selected {
+            continue;
+        }
+
+        // If the file is staged, first reset the index entry back to HEAD so
+        // the checkout below also wipes out the staged change, not just the
+        // worktree one.
+        if item.is_staged {
+            let target = repo.head()?.peel(git2::ObjectType::Commit)?;
+            repo.reset_default(Some(&target), &[&item.path])?;
+        }
+
+        let mut checkout_builder = git2::build::CheckoutBuilder::new();
+        checkout_builder
+            .force()
+            .remove_untracked(true)
+            .update_index(true)
+            .path(&item.path);
+
+        repo.checkout_head(Some(&mut checkout_builder))?;
+
+        logs.push(format!(
+            " - {} {}",
+            console::style("Discarded:").red(),
+            item.path.clone()
+        ));
+    }
+
+    println!("{}", logs.join("\n"));
+
+    Ok(())
+}
+
+/// Stages every change in the worktree in one shot, without prompting.
+/// Honors the repository's `status.showUntrackedFiles` config: when set to
+/// `no`, untracked files are left out of the index entirely; `normal`/`all`
+/// (or unset, which defaults to `all`) includes them.
+/// # Arguments
+/// * `repo` - A reference to the git repository.
+pub fn git_add_all(repo: &Repository) -> Result<(), git2::Error> {
+    let config = repo.config()?;
+    let show_untracked_files = config
+        .get_string("status.showuntrackedfiles")
+        .unwrap_or_else(|_| String::from("all"));
+
+    let mut index = repo.index()?;
+
+    if show_untracked_files == "no" {
+        let statuses = repo.statuses(None)?;
+        let untracked_paths: Vec<String> = statuses
+            .iter()
+            .filter(|entry| entry.status().contains(Status::WT_NEW))
+            .filter_map(|entry| entry.path().map(String::from))
+            .collect();
+
+        index.add_all(
+            ["*"].iter(),
+            IndexAddOption::DEFAULT,
+            Some(&mut |path: &Path, _matched_spec: &[u8]| -> i32 {
+                if untracked_paths.iter().any(|p| Path::new(p) == path) {
+                    1
+                } else {
+                    0
+                }
+            }),
+        )?;
+    } else {
+        index.add_all(["*"].iter(), IndexAddOption::DEFAULT, None)?;
+    }
+
+    index.write()?;
+
+    println!("{}", console::style("Staged all changes").green());
+
+    Ok(())
+}
+
 // Tests
 #[cfg(test)]
 mod tests {
@@ -236,7 +627,7 @@ fn test_get_paths_empty_worktree() {
     let (_tmp, repo) = init_repo();
 
     // No files, clean worktree
-    let statuses = get_paths(&repo).unwrap();
+    let statuses = get_paths(&repo, &StatusQueryOptions::default()).unwrap();
     assert!(statuses.is_empty());
 }
 
@@ -251,7 +642,7 @@ fn test_get_paths_unstaged_file() {
     writeln!(file, "hello world").unwrap();
 
     // Now, get_paths should return one PathItems with is_staged == false
-    let paths = get_paths(&repo).unwrap();
+    let paths = get_paths(&repo, &StatusQueryOptions::default()).unwrap();
     assert_eq!(paths.len(), 1);
     let item = &paths[0];
     assert_eq!(item.path, file_path);
@@ -274,7 +665,7 @@ fn test_get_paths_staged_file() {
     index.write().unwrap();
 
     // Now, get_paths should return one PathItems with is_staged == true
-    let paths = get_paths(&repo).unwrap();
+    let paths = get_paths(&repo, &StatusQueryOptions::default()).unwrap();
     assert_eq!(paths.len(), 1);
     let item = &paths[0];
     assert_eq!(item.path, file_path);
@@ -306,7 +697,7 @@ fn test_get_paths_staged_and_unstaged() {
     writeln!(unstaged_file, "unstaged content").unwrap();
 
     // Now, get_paths should return two PathItems
-    let mut paths = get_paths(&repo).unwrap();
+    let mut paths = get_paths(&repo, &StatusQueryOptions::default()).unwrap();
     paths.sort_by(|a, b| a.path.cmp(&b.path));
     assert_eq!(paths.len(), 2);
 
@@ -316,4 +707,110 @@ fn test_get_paths_staged_and_unstaged() {
     let unstaged = paths.iter().find(|p| p.path == unstaged_path).unwrap();
     assert!(!unstaged.is_staged);
 }
+
+#[test]
+fn test_status_code_untracked_and_staged() {
+    let (_tmp, repo) = init_repo();
+
+    let untracked_path = "untracked.txt";
+    let mut file = File::create(repo.workdir().unwrap().join(untracked_path)).unwrap();
+    writeln!(file, "new file").unwrap();
+
+    let staged_path = "staged.txt";
+    let mut staged_file = File::create(repo.workdir().unwrap().join(staged_path)).unwrap();
+    writeln!(staged_file, "staged content").unwrap();
+
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(staged_path)).unwrap();
+    index.write().unwrap();
+
+    let paths = get_paths(&repo, &StatusQueryOptions::default()).unwrap();
+
+    let untracked = paths.iter().find(|p| p.path == untracked_path).unwrap();
+    assert_eq!(untracked.status_code, "??");
+
+    let staged = paths.iter().find(|p| p.path == staged_path).unwrap();
+    assert_eq!(staged.status_code, "A ");
+}
+
+#[test]
+fn test_git_add_all_skips_untracked_when_configured() {
+    let (_tmp, repo) = init_repo();
+    repo.config()
+        .unwrap()
+        .set_str("status.showuntrackedfiles", "no")
+        .unwrap();
+
+    let tracked_path = "tracked.txt";
+    commit_file(&repo, tracked_path, "init", "init commit");
+    let mut file = File::create(repo.workdir().unwrap().join(tracked_path)).unwrap();
+    writeln!(file, "modified").unwrap();
+
+    let untracked_path = "untracked.txt";
+    File::create(repo.workdir().unwrap().join(untracked_path)).unwrap();
+
+    git_add_all(&repo).unwrap();
+
+    let index = repo.index().unwrap();
+    assert!(index.get_path(Path::new(tracked_path), 0).is_some());
+    assert!(index.get_path(Path::new(untracked_path), 0).is_none());
+}
+
+#[test]
+fn test_format_paths_porcelain_and_json() {
+    let (_tmp, repo) = init_repo();
+
+    let file_path = "foo.txt";
+    let mut file = File::create(repo.workdir().unwrap().join(file_path)).unwrap();
+    writeln!(file, "hello world").unwrap();
+
+    let paths = get_paths(&repo, &StatusQueryOptions::default()).unwrap();
+
+    let porcelain = format_paths(&paths, OutputFormat::Porcelain { nul_separated: false });
+    assert_eq!(porcelain, "?? foo.txt");
+
+    let json = format_paths(&paths, OutputFormat::Json);
+    assert_eq!(json, "[{\"path\":\"foo.txt\",\"status\":\"??\",\"staged\":false}]");
+}
+
+#[test]
+fn test_print_branch_summary_without_upstream() {
+    let (_tmp, repo) = init_repo();
+    commit_file(&repo, "init.txt", "init", "init commit");
+
+    // No upstream and a clean repo state; this should just print the
+    // branch name without erroring.
+    assert!(print_branch_summary(&repo).is_ok());
+}
+
+#[test]
+fn test_get_paths_respects_status_options() {
+    let (_tmp, repo) = init_repo();
+    commit_file(&repo, "init.txt", "init", "init commit");
+
+    let mut gitignore = File::create(repo.workdir().unwrap().join(".gitignore")).unwrap();
+    writeln!(gitignore, "ignored.txt").unwrap();
+    commit_file(&repo, ".gitignore", "ignored.txt\n", "add gitignore");
+
+    File::create(repo.workdir().unwrap().join("ignored.txt")).unwrap();
+    File::create(repo.workdir().unwrap().join("untracked.txt")).unwrap();
+
+    // Default options: untracked files show up, ignored files don't.
+    let default_paths = get_paths(&repo, &StatusQueryOptions::default()).unwrap();
+    assert!(default_paths.iter().any(|p| p.path == "untracked.txt"));
+    assert!(!default_paths.iter().any(|p| p.path == "ignored.txt"));
+
+    // Opting in to ignored files and opting out of untracked ones flips both.
+    let custom_paths = get_paths(
+        &repo,
+        &StatusQueryOptions {
+            include_untracked: false,
+            include_ignored: true,
+            ..StatusQueryOptions::default()
+        },
+    )
+    .unwrap();
+    assert!(!custom_paths.iter().any(|p| p.path == "untracked.txt"));
+    assert!(custom_paths.iter().any(|p| p.path == "ignored.txt"));
+}
 }
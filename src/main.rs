@@ -1,18 +1,97 @@
-use git_quick_add::{choose_files, get_paths, git_add_selected};
+use git_quick_add::{
+    choose_files, choose_files_live, choose_files_to_discard, format_paths, get_paths,
+    git_add_all, git_add_selected, git_discard_selected, print_branch_summary, OutputFormat,
+    StatusQueryOptions,
+};
 use git2::Repository;
-use std::process;
+use std::{env, process};
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
+    let discard_mode = args.iter().any(|arg| arg == "--discard");
+    let all_mode = args.iter().any(|arg| arg == "--all");
+    let porcelain_z_mode = args.iter().any(|arg| arg == "--porcelain-z" || arg == "-z");
+    let porcelain_mode = porcelain_z_mode || args.iter().any(|arg| arg == "--porcelain");
+    let json_mode = args.iter().any(|arg| arg == "--json");
+    let watch_mode = args.iter().any(|arg| arg == "--watch");
+
+    let status_options = StatusQueryOptions {
+        include_untracked: !args.iter().any(|arg| arg == "--no-untracked"),
+        include_ignored: args.iter().any(|arg| arg == "--ignored"),
+        ..StatusQueryOptions::default()
+    };
+
     let repo = Repository::open(".").unwrap_or_else(|_| {
         eprintln!("{}", console::style("Not a git repository").red());
         process::exit(1)
     });
 
-    let paths = get_paths(&repo).unwrap_or_else(|_| {
+    // Staging makes no sense on a bare repo, so reject it early instead of
+    // failing confusingly later when there's no workdir to diff against.
+    if repo.is_bare() {
+        eprintln!(
+            "{}",
+            console::style("Cannot stage files in a bare repository").red()
+        );
+        process::exit(1)
+    }
+
+    if all_mode {
+        git_add_all(&repo).unwrap_or_else(|_| {
+            eprintln!("{}", console::style("Failed to stage files").red());
+            process::exit(1)
+        });
+
+        return;
+    }
+
+    if watch_mode {
+        let _ = print_branch_summary(&repo);
+
+        let chosen = choose_files_live(&repo, &status_options).unwrap_or_else(|_| {
+            eprintln!("{}", console::style("No files found").red());
+            process::exit(1)
+        });
+
+        git_add_selected(&repo, &chosen).unwrap_or_else(|_| {
+            eprintln!("{}", console::style("Failed to stage files").red());
+            process::exit(1)
+        });
+
+        return;
+    }
+
+    let paths = get_paths(&repo, &status_options).unwrap_or_else(|_| {
         eprintln!("{}", console::style("No files found").red());
         process::exit(1)
     });
 
+    if json_mode {
+        println!("{}", format_paths(&paths, OutputFormat::Json));
+        return;
+    }
+
+    if porcelain_mode {
+        let format = OutputFormat::Porcelain {
+            nul_separated: porcelain_z_mode,
+        };
+        print!("{}", format_paths(&paths, format));
+        return;
+    }
+
+    let _ = print_branch_summary(&repo);
+
+    if discard_mode {
+        let chosen = choose_files_to_discard(paths);
+
+        git_discard_selected(&repo, &chosen).unwrap_or_else(|_| {
+            eprintln!("{}", console::style("Failed to discard files").red());
+            process::exit(1)
+        });
+
+        return;
+    }
+
     let chosen = choose_files(paths);
 
     git_add_selected(&repo, &chosen).unwrap_or_else(|_| {